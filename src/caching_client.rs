@@ -0,0 +1,346 @@
+//! Provides [`CachingVssClient`], a read-through caching wrapper around [`VssClient`].
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use crate::client::VssClient;
+use crate::error::VssError;
+use crate::types::{
+	DeleteObjectRequest, DeleteObjectResponse, GetObjectRequest, GetObjectResponse,
+	ListKeyVersionsRequest, ListKeyVersionsResponse, PutObjectRequest, PutObjectResponse,
+};
+use crate::util::retry::RetryPolicy;
+
+const DEFAULT_MAX_ENTRIES: usize = 1_000;
+
+/// Bounds on how large a [`CachingVssClient`]'s cache is allowed to grow before it starts
+/// evicting least-recently-used entries.
+#[derive(Clone, Copy, Debug)]
+pub enum CacheCapacity {
+	/// Evict the least-recently-used entry once the cache holds more than this many entries.
+	Entries(usize),
+	/// Evict least-recently-used entries once the cached values' combined size exceeds this many
+	/// bytes.
+	Bytes(usize),
+}
+
+/// Configuration for a [`CachingVssClient`].
+#[derive(Clone, Copy, Debug)]
+pub struct CacheConfig {
+	/// The cache's capacity. See [`CacheCapacity`].
+	pub capacity: CacheCapacity,
+	/// Whether caching is enabled at all.
+	///
+	/// Latency-sensitive callers that do many repeated reads of the same keys (e.g. config or
+	/// channel-manager blobs) benefit from leaving this `true`; callers who only ever read each
+	/// key once can set this `false` to make [`CachingVssClient`] behave as a pure passthrough
+	/// without paying for cache bookkeeping.
+	pub enabled: bool,
+}
+
+impl Default for CacheConfig {
+	fn default() -> Self {
+		Self { capacity: CacheCapacity::Entries(DEFAULT_MAX_ENTRIES), enabled: true }
+	}
+}
+
+type CacheKey = (String, String);
+
+struct CacheEntry {
+	value: GetObjectResponse,
+	version: i64,
+	size: usize,
+}
+
+struct Cache {
+	config: CacheConfig,
+	entries: HashMap<CacheKey, CacheEntry>,
+	/// Keys in least- to most-recently-used order.
+	lru_order: VecDeque<CacheKey>,
+	total_bytes: usize,
+}
+
+impl Cache {
+	fn new(config: CacheConfig) -> Self {
+		Self { config, entries: HashMap::new(), lru_order: VecDeque::new(), total_bytes: 0 }
+	}
+
+	fn get(&mut self, store_id: &str, key: &str) -> Option<GetObjectResponse> {
+		let cache_key = (store_id.to_owned(), key.to_owned());
+		let value = self.entries.get(&cache_key)?.value.clone();
+		self.touch(&cache_key);
+		Some(value)
+	}
+
+	fn touch(&mut self, cache_key: &CacheKey) {
+		if let Some(pos) = self.lru_order.iter().position(|k| k == cache_key) {
+			self.lru_order.remove(pos);
+		}
+		self.lru_order.push_back(cache_key.clone());
+	}
+
+	/// Inserts `value` for `(store_id, key)` at `version`, unless the same or a fresher version is
+	/// already cached (so a slow, in-flight read can't clobber a newer write).
+	fn insert(&mut self, store_id: &str, key: &str, version: i64, value: GetObjectResponse) {
+		let cache_key = (store_id.to_owned(), key.to_owned());
+		if let Some(existing) = self.entries.get(&cache_key) {
+			if existing.version >= version {
+				self.touch(&cache_key);
+				return;
+			}
+			self.total_bytes -= existing.size;
+		} else {
+			self.lru_order.push_back(cache_key.clone());
+		}
+
+		let size = Self::size_of(&value);
+		self.total_bytes += size;
+		self.entries.insert(cache_key.clone(), CacheEntry { value, version, size });
+		self.touch(&cache_key);
+		self.evict_over_capacity();
+	}
+
+	fn remove(&mut self, store_id: &str, key: &str) {
+		let cache_key = (store_id.to_owned(), key.to_owned());
+		if let Some(entry) = self.entries.remove(&cache_key) {
+			self.total_bytes -= entry.size;
+			if let Some(pos) = self.lru_order.iter().position(|k| k == &cache_key) {
+				self.lru_order.remove(pos);
+			}
+		}
+	}
+
+	fn evict_over_capacity(&mut self) {
+		let is_over_capacity = |entries_len: usize, total_bytes: usize| match self.config.capacity {
+			CacheCapacity::Entries(max) => entries_len > max,
+			CacheCapacity::Bytes(max) => total_bytes > max,
+		};
+		while is_over_capacity(self.entries.len(), self.total_bytes) {
+			let Some(lru_key) = self.lru_order.pop_front() else { break };
+			if let Some(entry) = self.entries.remove(&lru_key) {
+				self.total_bytes -= entry.size;
+			}
+		}
+	}
+
+	fn size_of(value: &GetObjectResponse) -> usize {
+		value.value.as_ref().map(|kv| kv.key.len() + kv.value.len()).unwrap_or(0)
+	}
+}
+
+/// A read-through, version-aware caching wrapper around [`VssClient`].
+///
+/// [`get_object`] is served from an in-memory LRU cache when the requested `(store_id, key)` is
+/// present. [`put_object`] and [`delete_object`] update or evict the affected entries based on the
+/// request itself (a written item is cached at the version it carries plus one), so the cache
+/// never serves data older than a write this client just performed. [`list_key_versions`] can additionally be used to cheaply validate the
+/// cache, dropping any entry whose server-side version has advanced since it was cached.
+///
+/// [`VssClient`]: crate::client::VssClient
+/// [`get_object`]: Self::get_object
+/// [`put_object`]: Self::put_object
+/// [`delete_object`]: Self::delete_object
+/// [`list_key_versions`]: Self::list_key_versions
+pub struct CachingVssClient<R>
+where
+	R: RetryPolicy<E = VssError>,
+{
+	inner: VssClient<R>,
+	cache: Mutex<Cache>,
+}
+
+impl<R: RetryPolicy<E = VssError>> CachingVssClient<R> {
+	/// Constructs a [`CachingVssClient`] wrapping `inner`, caching according to `config`.
+	pub fn new(inner: VssClient<R>, config: CacheConfig) -> Self {
+		Self { inner, cache: Mutex::new(Cache::new(config)) }
+	}
+
+	/// Returns the wrapped [`VssClient`], e.g. to bypass the cache for a single call.
+	pub fn inner(&self) -> &VssClient<R> {
+		&self.inner
+	}
+
+	/// Fetches a value against a given `key` in `request`, serving from cache when present.
+	/// Makes a service call to the `GetObject` endpoint of the VSS server on a cache miss.
+	/// For API contract/usage, refer to docs for [`GetObjectRequest`] and [`GetObjectResponse`].
+	pub async fn get_object(
+		&self, request: &GetObjectRequest,
+	) -> Result<GetObjectResponse, VssError> {
+		let cached = {
+			let mut cache = self.cache.lock().unwrap();
+			if cache.config.enabled {
+				cache.get(&request.store_id, &request.key)
+			} else {
+				None
+			}
+		};
+		if let Some(cached) = cached {
+			return Ok(cached);
+		}
+
+		let response = self.inner.get_object(request).await?;
+
+		if let Some(kv) = response.value.as_ref() {
+			let mut cache = self.cache.lock().unwrap();
+			if cache.config.enabled {
+				cache.insert(&request.store_id, &request.key, kv.version, response.clone());
+			}
+		}
+
+		Ok(response)
+	}
+
+	/// Writes multiple [`PutObjectRequest::transaction_items`] as part of a single transaction,
+	/// then updates the cache with the post-write versions (the server increments each item's
+	/// version by one on success) and evicts any [`PutObjectRequest::delete_items`].
+	/// Makes a service call to the `PutObject` endpoint of the VSS server.
+	/// For API contract/usage, refer to docs for [`PutObjectRequest`] and [`PutObjectResponse`].
+	pub async fn put_object(
+		&self, request: &PutObjectRequest,
+	) -> Result<PutObjectResponse, VssError> {
+		let response = self.inner.put_object(request).await?;
+
+		let mut cache = self.cache.lock().unwrap();
+		if cache.config.enabled {
+			for item in &request.transaction_items {
+				// VSS increments the stored version on a successful write, so the version now on
+				// the server is `item.version + 1`, not the pre-write version the request carried
+				// (`PutObjectResponse` doesn't echo it back). Cache that, in both the LRU's
+				// bookkeeping and the `KeyValue` we'd serve back from `get_object`, so a
+				// subsequent OCC write built on a cache hit isn't rejected as a spurious conflict.
+				let mut written = item.clone();
+				written.version = item.version + 1;
+				let value = GetObjectResponse { value: Some(written) };
+				cache.insert(&request.store_id, &item.key, item.version + 1, value);
+			}
+			for item in &request.delete_items {
+				cache.remove(&request.store_id, &item.key);
+			}
+		}
+
+		Ok(response)
+	}
+
+	/// Deletes the given `key` and `value` in `request`, evicting it from the cache.
+	/// Makes a service call to the `DeleteObject` endpoint of the VSS server.
+	/// For API contract/usage, refer to docs for [`DeleteObjectRequest`] and [`DeleteObjectResponse`].
+	pub async fn delete_object(
+		&self, request: &DeleteObjectRequest,
+	) -> Result<DeleteObjectResponse, VssError> {
+		let response = self.inner.delete_object(request).await?;
+
+		if let Some(key_value) = request.key_value.as_ref() {
+			let mut cache = self.cache.lock().unwrap();
+			if cache.config.enabled {
+				cache.remove(&request.store_id, &key_value.key);
+			}
+		}
+
+		Ok(response)
+	}
+
+	/// Lists keys and their corresponding version for a given [`ListKeyVersionsRequest::store_id`],
+	/// evicting any cached entry whose version is behind what the server reports.
+	/// Makes a service call to the `ListKeyVersions` endpoint of the VSS server.
+	/// For API contract/usage, refer to docs for [`ListKeyVersionsRequest`] and
+	/// [`ListKeyVersionsResponse`].
+	pub async fn list_key_versions(
+		&self, request: &ListKeyVersionsRequest,
+	) -> Result<ListKeyVersionsResponse, VssError> {
+		let response = self.inner.list_key_versions(request).await?;
+
+		let mut cache = self.cache.lock().unwrap();
+		if cache.config.enabled {
+			for kv in &response.key_versions {
+				let cache_key = (request.store_id.clone(), kv.key.clone());
+				let is_stale =
+					cache.entries.get(&cache_key).map(|e| e.version < kv.version).unwrap_or(false);
+				if is_stale {
+					cache.remove(&request.store_id, &kv.key);
+				}
+			}
+		}
+
+		Ok(response)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::KeyValue;
+
+	fn kv(key: &str, version: i64, value_len: usize) -> KeyValue {
+		KeyValue { key: key.to_owned(), version, value: vec![0u8; value_len] }
+	}
+
+	fn response(key: &str, version: i64, value_len: usize) -> GetObjectResponse {
+		GetObjectResponse { value: Some(kv(key, version, value_len)) }
+	}
+
+	#[test]
+	fn serves_a_cached_hit() {
+		let mut cache = Cache::new(CacheConfig::default());
+		cache.insert("store", "key", 1, response("key", 1, 3));
+		assert!(cache.get("store", "key").is_some());
+		// A different store_id is a distinct cache entry.
+		assert!(cache.get("other-store", "key").is_none());
+	}
+
+	#[test]
+	fn a_stale_write_never_clobbers_a_fresher_cached_version() {
+		let mut cache = Cache::new(CacheConfig::default());
+		cache.insert("store", "key", 5, response("key", 5, 3));
+		// A write that claims an older (or equal) version than what's cached is dropped.
+		cache.insert("store", "key", 4, response("key", 4, 9));
+		let cached = cache.get("store", "key").unwrap();
+		assert_eq!(cached.value.unwrap().version, 5);
+	}
+
+	#[test]
+	fn a_newer_write_replaces_the_cached_entry() {
+		let mut cache = Cache::new(CacheConfig::default());
+		cache.insert("store", "key", 1, response("key", 1, 3));
+		cache.insert("store", "key", 2, response("key", 2, 9));
+		let cached = cache.get("store", "key").unwrap();
+		assert_eq!(cached.value.unwrap().version, 2);
+	}
+
+	#[test]
+	fn remove_evicts_the_entry() {
+		let mut cache = Cache::new(CacheConfig::default());
+		cache.insert("store", "key", 1, response("key", 1, 3));
+		cache.remove("store", "key");
+		assert!(cache.get("store", "key").is_none());
+	}
+
+	#[test]
+	fn evicts_least_recently_used_at_entry_capacity() {
+		let config = CacheConfig { capacity: CacheCapacity::Entries(2), enabled: true };
+		let mut cache = Cache::new(config);
+		cache.insert("store", "a", 1, response("a", 1, 1));
+		cache.insert("store", "b", 1, response("b", 1, 1));
+		// Touch `a` so `b` becomes the least-recently-used entry.
+		assert!(cache.get("store", "a").is_some());
+		cache.insert("store", "c", 1, response("c", 1, 1));
+
+		assert!(cache.get("store", "a").is_some());
+		assert!(cache.get("store", "b").is_none());
+		assert!(cache.get("store", "c").is_some());
+	}
+
+	#[test]
+	fn evicts_least_recently_used_at_byte_capacity() {
+		let config = CacheConfig { capacity: CacheCapacity::Bytes(10), enabled: true };
+		let mut cache = Cache::new(config);
+		// Each entry is `key.len() + value.len()` bytes; "a" (1) + 4 bytes of value = 5 bytes.
+		cache.insert("store", "a", 1, response("a", 1, 4));
+		cache.insert("store", "b", 1, response("b", 1, 4));
+		// Inserting a third 5-byte entry pushes the cache over its 10-byte cap, evicting `a`.
+		cache.insert("store", "c", 1, response("c", 1, 4));
+
+		assert!(cache.get("store", "a").is_none());
+		assert!(cache.get("store", "b").is_some());
+		assert!(cache.get("store", "c").is_some());
+	}
+}
@@ -12,6 +12,7 @@ use crate::types::{
 	DeleteObjectRequest, DeleteObjectResponse, GetObjectRequest, GetObjectResponse,
 	ListKeyVersionsRequest, ListKeyVersionsResponse, PutObjectRequest, PutObjectResponse,
 };
+use crate::util::circuit_breaker::CircuitBreaker;
 use crate::util::retry::{retry, RetryPolicy};
 use crate::util::KeyValueVecKeyPrinter;
 
@@ -32,6 +33,7 @@ where
 	client: Client,
 	retry_policy: R,
 	header_provider: Arc<dyn VssHeaderProvider>,
+	circuit_breaker: Arc<CircuitBreaker>,
 }
 
 impl<R: RetryPolicy<E = VssError>> VssClient<R> {
@@ -48,6 +50,7 @@ impl<R: RetryPolicy<E = VssError>> VssClient<R> {
 			client,
 			retry_policy,
 			header_provider: Arc::new(FixedHeaders::new(HashMap::new())),
+			circuit_breaker: Arc::new(CircuitBreaker::default()),
 		}
 	}
 
@@ -58,7 +61,13 @@ impl<R: RetryPolicy<E = VssError>> VssClient<R> {
 		base_url: String, client: Client, retry_policy: R,
 		header_provider: Arc<dyn VssHeaderProvider>,
 	) -> Self {
-		Self { base_url, client, retry_policy, header_provider }
+		Self {
+			base_url,
+			client,
+			retry_policy,
+			header_provider,
+			circuit_breaker: Arc::new(CircuitBreaker::default()),
+		}
 	}
 
 	/// Constructs a [`VssClient`] using `base_url` as the VSS server endpoint.
@@ -68,7 +77,13 @@ impl<R: RetryPolicy<E = VssError>> VssClient<R> {
 		base_url: String, retry_policy: R, header_provider: Arc<dyn VssHeaderProvider>,
 	) -> Self {
 		let client = Client::new(DEFAULT_CLIENT_CAPACITY);
-		Self { base_url, client, retry_policy, header_provider }
+		Self {
+			base_url,
+			client,
+			retry_policy,
+			header_provider,
+			circuit_breaker: Arc::new(CircuitBreaker::default()),
+		}
 	}
 
 	/// Returns the underlying base URL.
@@ -76,6 +91,13 @@ impl<R: RetryPolicy<E = VssError>> VssClient<R> {
 		&self.base_url
 	}
 
+	/// Uses the given `circuit_breaker`, instead of the default one, to guard requests made by
+	/// this client. Shareable across cloned [`VssClient`]s since it is held behind an [`Arc`].
+	pub fn with_circuit_breaker(mut self, circuit_breaker: Arc<CircuitBreaker>) -> Self {
+		self.circuit_breaker = circuit_breaker;
+		self
+	}
+
 	/// Fetches a value against a given `key` in `request`.
 	/// Makes a service call to the `GetObject` endpoint of the VSS server.
 	/// For API contract/usage, refer to docs for [`GetObjectRequest`] and [`GetObjectResponse`].
@@ -192,6 +214,21 @@ impl<R: RetryPolicy<E = VssError>> VssClient<R> {
 
 	async fn post_request<Rq: Message, Rs: Message + Default>(
 		&self, request: &Rq, url: &str, enable_pipelining: bool,
+	) -> Result<Rs, VssError> {
+		let authority = authority_of(url);
+		if !self.circuit_breaker.allow_request(&authority) {
+			return Err(VssError::CircuitBreakerOpen(format!(
+				"circuit breaker open for {authority}"
+			)));
+		}
+
+		let result = self.send_request(request, url, enable_pipelining).await;
+		self.circuit_breaker.record_result(&authority, result.as_ref().map(|_| ()));
+		result
+	}
+
+	async fn send_request<Rq: Message, Rs: Message + Default>(
+		&self, request: &Rq, url: &str, enable_pipelining: bool,
 	) -> Result<Rs, VssError> {
 		let request_body = request.encode_to_vec();
 		let headers = self
@@ -224,3 +261,10 @@ impl<R: RetryPolicy<E = VssError>> VssClient<R> {
 		}
 	}
 }
+
+/// Extracts the authority (host, optionally with port) from `url`, used to key the
+/// [`CircuitBreaker`] per-endpoint.
+fn authority_of(url: &str) -> String {
+	let without_scheme = url.split("://").nth(1).unwrap_or(url);
+	without_scheme.split('/').next().unwrap_or(without_scheme).to_owned()
+}
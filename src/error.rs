@@ -0,0 +1,94 @@
+//! Provides [`VssError`], the common error type returned by [`VssClient`] operations.
+//!
+//! [`VssClient`]: crate::client::VssClient
+
+use std::fmt;
+
+/// Errors that may occur when interacting with a VSS server via [`VssClient`].
+///
+/// [`VssClient`]: crate::client::VssClient
+#[derive(Debug)]
+pub enum VssError {
+	/// The given key could not be found. Corresponds to an HTTP `404` response.
+	NoSuchKeyError(String),
+	/// The write conflicted with the current version of the key on the server. Corresponds to an
+	/// HTTP `409` response.
+	ConflictError(String),
+	/// The server rejected the request as invalid, including a failed authentication/authorization
+	/// check (HTTP `401`/`403`). Corresponds to an HTTP `4xx` response not covered by a more
+	/// specific variant above; carries the real status code so callers (e.g. a circuit breaker's
+	/// [`Allow4xxBelow`]) can distinguish between them.
+	///
+	/// [`Allow4xxBelow`]: crate::util::circuit_breaker::FailureClassifier::Allow4xxBelow
+	InvalidRequestError(u16, String),
+	/// The request could not be authenticated locally, because the configured
+	/// [`VssHeaderProvider`] failed before the request could even be sent. Unlike the other
+	/// variants above, this never carries an HTTP status code, since no response was received.
+	///
+	/// [`VssHeaderProvider`]: crate::headers::VssHeaderProvider
+	AuthError(String),
+	/// The server encountered an internal error, or otherwise violated the expected API contract.
+	/// Corresponds to an HTTP `5xx` response.
+	InternalServerError(String),
+	/// The underlying HTTP request could not be completed at all, e.g. due to a connection error.
+	RequestError(String),
+	/// The request was rejected locally by a circuit breaker without being attempted, because the
+	/// target host has recently been failing.
+	///
+	/// [`CircuitBreaker`]: crate::util::circuit_breaker::CircuitBreaker
+	CircuitBreakerOpen(String),
+}
+
+impl VssError {
+	pub(crate) fn new(status_code: u16, payload: Vec<u8>) -> Self {
+		let body = String::from_utf8_lossy(&payload).into_owned();
+		match status_code {
+			404 => VssError::NoSuchKeyError(body),
+			409 => VssError::ConflictError(body),
+			400..=499 => VssError::InvalidRequestError(status_code, body),
+			_ => VssError::InternalServerError(body),
+		}
+	}
+
+	/// Returns the HTTP status code this error was constructed from, if it originated from an
+	/// HTTP `4xx` response. Returns `None` for [`VssError::InternalServerError`], which does not
+	/// keep the status code of the response it came from, and for errors that never reached the
+	/// server, e.g. [`VssError::AuthError`], [`VssError::RequestError`], and
+	/// [`VssError::CircuitBreakerOpen`].
+	pub fn status_code(&self) -> Option<u16> {
+		match self {
+			VssError::NoSuchKeyError(_) => Some(404),
+			VssError::ConflictError(_) => Some(409),
+			VssError::InvalidRequestError(code, _) => Some(*code),
+			_ => None,
+		}
+	}
+}
+
+impl fmt::Display for VssError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			VssError::NoSuchKeyError(msg) => write!(f, "NoSuchKeyError: {msg}"),
+			VssError::ConflictError(msg) => write!(f, "ConflictError: {msg}"),
+			VssError::InvalidRequestError(code, msg) => write!(f, "InvalidRequestError({code}): {msg}"),
+			VssError::AuthError(msg) => write!(f, "AuthError: {msg}"),
+			VssError::InternalServerError(msg) => write!(f, "InternalServerError: {msg}"),
+			VssError::RequestError(msg) => write!(f, "RequestError: {msg}"),
+			VssError::CircuitBreakerOpen(msg) => write!(f, "CircuitBreakerOpen: {msg}"),
+		}
+	}
+}
+
+impl std::error::Error for VssError {}
+
+impl From<bitreq::Error> for VssError {
+	fn from(e: bitreq::Error) -> Self {
+		VssError::RequestError(e.to_string())
+	}
+}
+
+impl From<prost::DecodeError> for VssError {
+	fn from(e: prost::DecodeError) -> Self {
+		VssError::InternalServerError(e.to_string())
+	}
+}
@@ -0,0 +1,159 @@
+//! Provides the [`LnurlAuthJwtProvider`].
+
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use bitcoin::hashes::hex::FromHex;
+use bitcoin::hashes::{hmac, sha256, Hash as _, HashEngine as _};
+use bitcoin::secp256k1::{Message, Scalar, Secp256k1, SecretKey, SignOnly};
+use serde::Deserialize;
+use tokio::sync::Mutex;
+
+use crate::headers::{VssHeaderProvider, VssHeaderProviderError};
+
+/// How far ahead of a cached JWT's expiry we proactively mint a new one, so that a request never
+/// races a token expiring mid-flight.
+const EXPIRY_MARGIN_SECS: u64 = 60;
+
+#[derive(Deserialize)]
+struct ChallengeResponse {
+	k1: String,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+	token: String,
+	exp: u64,
+}
+
+struct CachedToken {
+	jwt: String,
+	exp: u64,
+}
+
+/// A [`VssHeaderProvider`] which authenticates via an LNURL-Auth-style challenge/response and
+/// injects the resulting bearer JWT as the `Authorization` header.
+///
+/// On first use, and whenever the cached token is missing or close to expiry, this provider:
+/// 1. fetches a `k1` challenge from `<token_endpoint>/challenge`,
+/// 2. derives a linking key from the [`SecretKey`] it was constructed with (as in LUD-04) and
+///    signs the challenge with it using `secp256k1` ECDSA,
+/// 3. exchanges the linking public key, challenge, and signature for a signed JWT, which is
+///    cached alongside its `exp`.
+///
+/// Because minting a token this way is comparatively expensive, and VSS requests are frequent,
+/// the token is cached behind an async-aware lock and reused by [`get_headers`] until it nears
+/// expiry, rather than re-authenticating on every call the way [`SigsAuthProvider`] does.
+///
+/// [`get_headers`]: VssHeaderProvider::get_headers
+/// [`SigsAuthProvider`]: crate::headers::SigsAuthProvider
+pub struct LnurlAuthJwtProvider {
+	key: SecretKey,
+	secp_ctx: Secp256k1<SignOnly>,
+	token_endpoint: String,
+	default_headers: HashMap<String, String>,
+	client: bitreq::Client,
+	cached_token: Mutex<Option<CachedToken>>,
+}
+
+impl LnurlAuthJwtProvider {
+	/// Creates a new [`LnurlAuthJwtProvider`] which authenticates against `token_endpoint` using
+	/// `key` to derive its linking key.
+	///
+	/// In addition to the automatically-added `Authorization` header, any headers provided in
+	/// `default_headers` (except an `Authorization` header) will be added to the headers list.
+	pub fn new(
+		key: SecretKey, token_endpoint: String, default_headers: HashMap<String, String>,
+	) -> Self {
+		Self {
+			key,
+			secp_ctx: Secp256k1::signing_only(),
+			token_endpoint,
+			default_headers,
+			client: bitreq::Client::new(1),
+			cached_token: Mutex::new(None),
+		}
+	}
+
+	/// Derives the LUD-04 linking key for `self.token_endpoint`, so that a distinct, unlinkable
+	/// key is used per relying party rather than reusing `self.key` directly.
+	fn linking_key(&self) -> SecretKey {
+		let hashing_key = sha256::Hash::hash(&self.key.secret_bytes());
+		let mut engine = hmac::HmacEngine::<sha256::Hash>::new(hashing_key.as_ref());
+		engine.input(self.token_endpoint.as_bytes());
+		let tweak = hmac::Hmac::<sha256::Hash>::from_engine(engine);
+		let scalar = Scalar::from_be_bytes(tweak.to_byte_array())
+			.expect("a SHA-256 output is always a valid scalar");
+		self.key.add_tweak(&scalar).expect("negligible probability of an invalid resulting key")
+	}
+
+	async fn mint_token(&self) -> Result<CachedToken, VssHeaderProviderError> {
+		let to_err = |e: bitreq::Error| VssHeaderProviderError::new(e.to_string());
+		let to_json_err = |e: serde_json::Error| VssHeaderProviderError::new(e.to_string());
+
+		let challenge_url = format!("{}/challenge", self.token_endpoint);
+		let challenge_resp =
+			self.client.send_async(bitreq::get(&challenge_url)).await.map_err(to_err)?;
+		let challenge: ChallengeResponse =
+			serde_json::from_slice(&challenge_resp.into_bytes()).map_err(to_json_err)?;
+		let k1 = Vec::from_hex(&challenge.k1)
+			.map_err(|e| VssHeaderProviderError::new(e.to_string()))?;
+
+		let linking_key = self.linking_key();
+		let linking_pubkey = linking_key.public_key(&self.secp_ctx);
+		let msg = Message::from_digest_slice(&k1)
+			.map_err(|e| VssHeaderProviderError::new(e.to_string()))?;
+		let sig = self.secp_ctx.sign_ecdsa(&msg, &linking_key);
+
+		let body = serde_json::json!({
+			"key": linking_pubkey.to_string(),
+			"k1": challenge.k1,
+			"sig": sig.serialize_der().to_string(),
+		})
+		.to_string();
+
+		let token_resp = self
+			.client
+			.send_async(bitreq::post(&self.token_endpoint).with_body(body.into_bytes()))
+			.await
+			.map_err(to_err)?;
+		let token: TokenResponse =
+			serde_json::from_slice(&token_resp.into_bytes()).map_err(to_json_err)?;
+
+		Ok(CachedToken { jwt: token.token, exp: token.exp })
+	}
+
+	fn is_near_expiry(cached: &CachedToken, now: u64) -> bool {
+		cached.exp <= now.saturating_add(EXPIRY_MARGIN_SECS)
+	}
+
+	fn headers_for(&self, jwt: &str) -> HashMap<String, String> {
+		let mut headers = self.default_headers.clone();
+		headers.insert("Authorization".to_owned(), format!("Bearer {}", jwt));
+		headers
+	}
+}
+
+#[async_trait]
+impl VssHeaderProvider for LnurlAuthJwtProvider {
+	async fn get_headers(
+		&self, _request: &[u8],
+	) -> Result<HashMap<String, String>, VssHeaderProviderError> {
+		let now = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.expect("System time must be at least Jan 1, 1970")
+			.as_secs();
+
+		let mut cached = self.cached_token.lock().await;
+		let needs_refresh = match cached.as_ref() {
+			Some(token) => Self::is_near_expiry(token, now),
+			None => true,
+		};
+		if needs_refresh {
+			*cached = Some(self.mint_token().await?);
+		}
+
+		Ok(self.headers_for(&cached.as_ref().expect("just populated above").jwt))
+	}
+}
@@ -0,0 +1,70 @@
+//! Provides [`VssHeaderProvider`], the trait used to customize the HTTP headers (most commonly
+//! for authentication) sent alongside every [`VssClient`] request, along with a couple of default
+//! implementations.
+//!
+//! [`VssClient`]: crate::client::VssClient
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::fmt;
+
+mod lnurl_auth;
+mod sigs_auth;
+
+pub use lnurl_auth::LnurlAuthJwtProvider;
+pub use sigs_auth::{SigsAuthProvider, SIGNING_CONSTANT};
+
+/// A trait used to supply the HTTP headers that should be added to every request made by a
+/// [`VssClient`].
+///
+/// [`VssClient`]: crate::client::VssClient
+#[async_trait]
+pub trait VssHeaderProvider: Send + Sync {
+	/// Returns the HTTP headers to add to a request carrying the given, already-serialized
+	/// `request` body.
+	async fn get_headers(
+		&self, request: &[u8],
+	) -> Result<HashMap<String, String>, VssHeaderProviderError>;
+}
+
+/// Error returned by [`VssHeaderProvider::get_headers`].
+#[derive(Debug)]
+pub struct VssHeaderProviderError {
+	msg: String,
+}
+
+impl VssHeaderProviderError {
+	/// Constructs a new [`VssHeaderProviderError`] carrying the given message.
+	pub fn new(msg: String) -> Self {
+		Self { msg }
+	}
+}
+
+impl fmt::Display for VssHeaderProviderError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.msg)
+	}
+}
+
+impl std::error::Error for VssHeaderProviderError {}
+
+/// A [`VssHeaderProvider`] which always returns a fixed set of headers.
+pub struct FixedHeaders {
+	headers: HashMap<String, String>,
+}
+
+impl FixedHeaders {
+	/// Constructs a new [`FixedHeaders`] which will always provide the given `headers`.
+	pub fn new(headers: HashMap<String, String>) -> Self {
+		Self { headers }
+	}
+}
+
+#[async_trait]
+impl VssHeaderProvider for FixedHeaders {
+	async fn get_headers(
+		&self, _request: &[u8],
+	) -> Result<HashMap<String, String>, VssHeaderProviderError> {
+		Ok(self.headers.clone())
+	}
+}
@@ -15,17 +15,22 @@ use std::time::SystemTime;
 pub const SIGNING_CONSTANT: &'static [u8] =
 	b"VSS Signature Authorizer Signing Salt Constant..................";
 
-fn build_token(secret_key: &SecretKey, secp_ctx: &Secp256k1<SignOnly>) -> String {
+fn build_token(
+	secret_key: &SecretKey, secp_ctx: &Secp256k1<SignOnly>, body_digest: Option<&[u8; 32]>,
+) -> String {
 	let pubkey = secret_key.public_key(secp_ctx);
 	let old_time = "System time must be at least Jan 1, 1970";
 	let now = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).expect(old_time).as_secs();
 
 	// 2^64 serialized as a string is 20 bytes.
-	let mut buffer = [0u8; SIGNING_CONSTANT.len() + 33 + 20];
+	let mut buffer = [0u8; SIGNING_CONSTANT.len() + 33 + 20 + 32];
 	let mut stream = &mut buffer[..];
 	stream.write_all(SIGNING_CONSTANT).unwrap();
 	stream.write_all(&pubkey.serialize()).unwrap();
 	write!(stream, "{now}").unwrap();
+	if let Some(digest) = body_digest {
+		stream.write_all(digest).unwrap();
+	}
 	let bytes_remaining = stream.len();
 	let bytes_to_sign = &buffer[..buffer.len() - bytes_remaining];
 
@@ -40,6 +45,24 @@ fn build_token(secret_key: &SecretKey, secp_ctx: &Secp256k1<SignOnly>) -> String
 	out
 }
 
+/// Encodes `bytes` as standard (`+`/`/`, padded) base64, as used by the `Digest` header.
+fn base64_encode(bytes: &[u8]) -> String {
+	const TABLE: &[u8; 64] =
+		b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+	let mut out = String::with_capacity((bytes.len() + 2) / 3 * 4);
+	for chunk in bytes.chunks(3) {
+		let b0 = chunk[0] as u32;
+		let b1 = *chunk.get(1).unwrap_or(&0) as u32;
+		let b2 = *chunk.get(2).unwrap_or(&0) as u32;
+		let n = (b0 << 16) | (b1 << 8) | b2;
+		out.push(TABLE[(n >> 18 & 0x3F) as usize] as char);
+		out.push(TABLE[(n >> 12 & 0x3F) as usize] as char);
+		out.push(if chunk.len() > 1 { TABLE[(n >> 6 & 0x3F) as usize] as char } else { '=' });
+		out.push(if chunk.len() > 2 { TABLE[(n & 0x3F) as usize] as char } else { '=' });
+	}
+	out
+}
+
 /// A simple auth provider which simply proves knowledge of a private key.
 ///
 /// It provides a good default authentication mechanism for testing, or in the case that
@@ -49,6 +72,7 @@ pub struct SigsAuthProvider {
 	key: SecretKey,
 	secp_ctx: Secp256k1<SignOnly>,
 	default_headers: HashMap<String, String>,
+	bind_to_body: bool,
 }
 
 impl SigsAuthProvider {
@@ -57,22 +81,71 @@ impl SigsAuthProvider {
 	/// This provides an incredibly simple authentication scheme and allows the server to ensure
 	/// data for separate clients is kept separate, without any application-specific logic.
 	///
+	/// The resulting `Authorization` token proves only key ownership and timestamp, so it is
+	/// replayable against any endpoint/payload within its validity window. Use
+	/// [`new_with_body_digest`] if the VSS server should reject mismatched or replayed bodies.
+	///
 	/// In addition to the automatically-added `Authorization` header, any headers provided in
 	/// `default_headers` (except an `Authorization` header) will be added to the headers list.
+	///
+	/// [`new_with_body_digest`]: Self::new_with_body_digest
 	pub fn new(key: SecretKey, default_headers: HashMap<String, String>) -> Self {
-		SigsAuthProvider { secp_ctx: Secp256k1::signing_only(), key, default_headers }
+		SigsAuthProvider { secp_ctx: Secp256k1::signing_only(), key, default_headers, bind_to_body: false }
+	}
+
+	/// Creates a new auth provider which, in addition to proving knowledge of `key`, binds each
+	/// signed token to the body of the request it accompanies.
+	///
+	/// A SHA-256 digest of the request body is emitted as a `Digest: sha-256=<base64>` header and
+	/// folded into the signed material, so the signature can no longer be replayed against a
+	/// different call. Only use this with VSS servers that are configured to verify the `Digest`
+	/// header against the signed token; since the digest is part of the signed material, a server
+	/// that checks the signature without it will reject these tokens, and a server that verifies
+	/// it will reject tokens minted via [`new`].
+	///
+	/// [`new`]: Self::new
+	pub fn new_with_body_digest(key: SecretKey, default_headers: HashMap<String, String>) -> Self {
+		SigsAuthProvider { secp_ctx: Secp256k1::signing_only(), key, default_headers, bind_to_body: true }
 	}
 }
 
 #[async_trait]
 impl VssHeaderProvider for SigsAuthProvider {
 	async fn get_headers(
-		&self, _request: &[u8],
+		&self, request: &[u8],
 	) -> Result<HashMap<String, String>, VssHeaderProviderError> {
 		// TODO: We might consider not re-signing on every request, but its cheap enough that it
 		// doesn't really matter
 		let mut headers = self.default_headers.clone();
-		headers.insert("Authorization".to_owned(), build_token(&self.key, &self.secp_ctx));
+		if self.bind_to_body {
+			let digest = Sha256::hash(request);
+			headers.insert(
+				"Digest".to_owned(),
+				format!("sha-256={}", base64_encode(digest.to_byte_array().as_slice())),
+			);
+			headers.insert(
+				"Authorization".to_owned(),
+				build_token(&self.key, &self.secp_ctx, Some(&digest.to_byte_array())),
+			);
+		} else {
+			headers.insert("Authorization".to_owned(), build_token(&self.key, &self.secp_ctx, None));
+		}
 		Ok(headers)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::base64_encode;
+
+	#[test]
+	fn base64_encode_known_vectors() {
+		assert_eq!(base64_encode(b""), "");
+		assert_eq!(base64_encode(b"f"), "Zg==");
+		assert_eq!(base64_encode(b"fo"), "Zm8=");
+		assert_eq!(base64_encode(b"foo"), "Zm9v");
+		assert_eq!(base64_encode(b"foob"), "Zm9vYg==");
+		assert_eq!(base64_encode(b"fooba"), "Zm9vYmE=");
+		assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+	}
+}
@@ -0,0 +1,42 @@
+#![deny(missing_docs)]
+
+//! A client for the Versioned Storage Service (VSS) — a cloud-agnostic key-value store with
+//! optimistic concurrency control, designed to allow LDK clients to securely back up channel
+//! state with a cloud provider.
+//!
+//! [`VssClient`] is the thin client used to make calls against a hosted VSS instance.
+//! [`CachingVssClient`] wraps it with a read-through cache for latency-sensitive callers.
+//!
+//! [`VssClient`]: client::VssClient
+//! [`CachingVssClient`]: caching_client::CachingVssClient
+
+/// Provides [`CachingVssClient`], a read-through caching wrapper around [`VssClient`].
+///
+/// [`CachingVssClient`]: caching_client::CachingVssClient
+/// [`VssClient`]: client::VssClient
+pub mod caching_client;
+
+/// Provides [`VssClient`], the thin-client used to access a hosted VSS instance.
+///
+/// [`VssClient`]: client::VssClient
+pub mod client;
+
+/// Provides [`VssError`], the common error type returned by [`VssClient`] operations.
+///
+/// [`VssError`]: error::VssError
+/// [`VssClient`]: client::VssClient
+pub mod error;
+
+/// Provides [`VssHeaderProvider`] and a couple of default implementations.
+///
+/// [`VssHeaderProvider`]: headers::VssHeaderProvider
+pub mod headers;
+
+/// Protobuf-generated request/response types used by the VSS API.
+pub mod types {
+	#![allow(missing_docs)]
+	include!(concat!(env!("OUT_DIR"), "/types.rs"));
+}
+
+/// Miscellaneous utilities used across the crate.
+pub mod util;
@@ -0,0 +1,321 @@
+//! Provides [`CircuitBreaker`], a per-authority breaker that [`VssClient::post_request`] consults
+//! before each attempt, composing with [`RetryPolicy`] rather than replacing it.
+//!
+//! [`VssClient::post_request`]: crate::client::VssClient
+//! [`RetryPolicy`]: super::retry::RetryPolicy
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::error::VssError;
+
+const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+const DEFAULT_WINDOW: Duration = Duration::from_secs(30);
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(5);
+const MAX_COOLDOWN: Duration = Duration::from_secs(5 * 60);
+
+/// Determines which outcomes of a request count as failures for the purpose of tripping a
+/// [`CircuitBreaker`].
+#[derive(Clone, Copy, Debug)]
+pub enum FailureClassifier {
+	/// Only a `2xx` response is a success; every other outcome, including expected client errors,
+	/// counts as a failure.
+	Require2xx,
+	/// Any outcome whose HTTP status code is below `code` is a success; status codes at or above
+	/// `code`, and any error without a status code (see [`VssError::status_code`], which is `None`
+	/// for a `5xx` [`VssError::InternalServerError`] too), count as a failure. Useful for ignoring expected client
+	/// errors (e.g. a `404`/`NoSuchKey` from [`get_object`]) that shouldn't trip the breaker.
+	///
+	/// [`get_object`]: crate::client::VssClient::get_object
+	Allow4xxBelow(u16),
+}
+
+impl FailureClassifier {
+	fn is_failure(&self, result: &Result<(), &VssError>) -> bool {
+		let error = match result {
+			Ok(()) => return false,
+			Err(e) => e,
+		};
+		match self {
+			FailureClassifier::Require2xx => true,
+			FailureClassifier::Allow4xxBelow(code) => {
+				error.status_code().map(|sc| sc >= *code).unwrap_or(true)
+			},
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+	Closed,
+	Open,
+	/// A single probe has been let through; further requests are rejected until it resolves.
+	HalfOpen,
+}
+
+struct BreakerState {
+	state: State,
+	consecutive_failures: u32,
+	window_start: Instant,
+	opened_at: Instant,
+	cooldown: Duration,
+}
+
+impl BreakerState {
+	fn new(initial_cooldown: Duration) -> Self {
+		let now = Instant::now();
+		Self {
+			state: State::Closed,
+			consecutive_failures: 0,
+			window_start: now,
+			opened_at: now,
+			cooldown: initial_cooldown,
+		}
+	}
+}
+
+/// A circuit breaker, keyed by request authority (host), that stops a hard-down VSS server from
+/// absorbing full [`RetryPolicy`] retry storms.
+///
+/// A breaker starts `Closed`. It trips to `Open` after `failure_threshold` consecutive failures
+/// (as judged by its [`FailureClassifier`]) counted within the current window, and rejects
+/// requests immediately while open. The window is fixed, not sliding: the first one starts when
+/// the breaker for an authority is created, and a failure arriving more than `window` after the
+/// current one started begins a new window, restarting the count at one. A success resets the
+/// count but not the window. After its cooldown elapses it moves to `HalfOpen` and allows a single
+/// probe through: a successful probe closes the breaker, a failed one re-opens it with the
+/// cooldown doubled (up to a cap).
+///
+/// Shareable across cloned [`VssClient`]s behind an [`Arc`].
+///
+/// [`RetryPolicy`]: super::retry::RetryPolicy
+/// [`VssClient`]: crate::client::VssClient
+/// [`Arc`]: std::sync::Arc
+pub struct CircuitBreaker {
+	classifier: FailureClassifier,
+	failure_threshold: u32,
+	window: Duration,
+	initial_cooldown: Duration,
+	breakers: Mutex<HashMap<String, BreakerState>>,
+}
+
+impl CircuitBreaker {
+	/// Constructs a new [`CircuitBreaker`] which trips after `failure_threshold` consecutive
+	/// failures (as determined by `classifier`) counted within one fixed `window` (see
+	/// [`CircuitBreaker`] for when a window starts), initially cooling down
+	/// for `initial_cooldown` before probing again (subsequent re-opens double this, up to a cap).
+	pub fn new(
+		classifier: FailureClassifier, failure_threshold: u32, window: Duration,
+		initial_cooldown: Duration,
+	) -> Self {
+		Self {
+			classifier,
+			failure_threshold,
+			window,
+			initial_cooldown,
+			breakers: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Returns whether a request to `authority` may proceed right now. Transitions a tripped
+	/// breaker from `Open` to `HalfOpen` once its cooldown has elapsed, admitting a single probe.
+	pub(crate) fn allow_request(&self, authority: &str) -> bool {
+		let mut breakers = self.breakers.lock().unwrap();
+		let breaker = breakers
+			.entry(authority.to_owned())
+			.or_insert_with(|| BreakerState::new(self.initial_cooldown));
+		match breaker.state {
+			State::Closed => true,
+			State::HalfOpen => false,
+			State::Open => {
+				if breaker.opened_at.elapsed() >= breaker.cooldown {
+					breaker.state = State::HalfOpen;
+					true
+				} else {
+					false
+				}
+			},
+		}
+	}
+
+	/// Records the outcome of a single attempt against `authority`, tripping, probing, or closing
+	/// the breaker as appropriate. `post_request` calls this once per network attempt, before the
+	/// `RetryPolicy` decides whether to retry, not once for the overall (possibly retried)
+	/// operation.
+	pub(crate) fn record_result(&self, authority: &str, result: Result<(), &VssError>) {
+		let mut breakers = self.breakers.lock().unwrap();
+		let breaker = breakers
+			.entry(authority.to_owned())
+			.or_insert_with(|| BreakerState::new(self.initial_cooldown));
+		let is_failure = self.classifier.is_failure(&result);
+
+		match breaker.state {
+			State::HalfOpen => {
+				if is_failure {
+					breaker.state = State::Open;
+					breaker.opened_at = Instant::now();
+					breaker.cooldown = (breaker.cooldown * 2).min(MAX_COOLDOWN);
+				} else {
+					breaker.state = State::Closed;
+					breaker.consecutive_failures = 0;
+					breaker.cooldown = self.initial_cooldown;
+				}
+			},
+			State::Closed => {
+				if is_failure {
+					let now = Instant::now();
+					if now.duration_since(breaker.window_start) > self.window {
+						breaker.window_start = now;
+						breaker.consecutive_failures = 0;
+					}
+					breaker.consecutive_failures += 1;
+					if breaker.consecutive_failures >= self.failure_threshold {
+						breaker.state = State::Open;
+						breaker.opened_at = now;
+					}
+				} else {
+					breaker.consecutive_failures = 0;
+				}
+			},
+			// A request admitted while the breaker was still `Closed` can finish after other
+			// concurrent requests to the same authority have tripped it. The breaker is already
+			// open, so that late outcome is ignored.
+			State::Open => {},
+		}
+	}
+}
+
+impl Default for CircuitBreaker {
+	/// Constructs a [`CircuitBreaker`] which tolerates expected client errors like a `404`/
+	/// `NoSuchKey` from `get_object` or a `409`/conflict from an optimistic-concurrency write —
+	/// i.e. `Allow4xxBelow(500)` — tripping after 5 consecutive `5xx`/transport failures within a
+	/// 30 second window. A normal first-run/polling access pattern against missing or conflicting
+	/// keys should never trip the default breaker; use [`CircuitBreaker::new`] with
+	/// [`FailureClassifier::Require2xx`] if that stricter behavior is actually wanted.
+	fn default() -> Self {
+		Self::new(
+			FailureClassifier::Allow4xxBelow(500),
+			DEFAULT_FAILURE_THRESHOLD,
+			DEFAULT_WINDOW,
+			DEFAULT_COOLDOWN,
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::error::VssError;
+
+	fn err() -> VssError {
+		VssError::InternalServerError("boom".to_owned())
+	}
+
+	#[test]
+	fn closed_until_threshold_then_open() {
+		let breaker = CircuitBreaker::new(
+			FailureClassifier::Require2xx,
+			3,
+			Duration::from_secs(30),
+			Duration::from_secs(60),
+		);
+
+		for _ in 0..2 {
+			assert!(breaker.allow_request("host"));
+			breaker.record_result("host", Err(&err()));
+		}
+		// Still below the threshold of 3 consecutive failures.
+		assert!(breaker.allow_request("host"));
+		breaker.record_result("host", Err(&err()));
+
+		// The third consecutive failure trips the breaker.
+		assert!(!breaker.allow_request("host"));
+	}
+
+	#[test]
+	fn a_success_resets_the_consecutive_failure_count() {
+		let breaker = CircuitBreaker::new(
+			FailureClassifier::Require2xx,
+			2,
+			Duration::from_secs(30),
+			Duration::from_secs(60),
+		);
+
+		assert!(breaker.allow_request("host"));
+		breaker.record_result("host", Err(&err()));
+		assert!(breaker.allow_request("host"));
+		breaker.record_result("host", Ok(()));
+
+		// The failure streak was reset by the success, so one more failure shouldn't trip it.
+		assert!(breaker.allow_request("host"));
+		breaker.record_result("host", Err(&err()));
+		assert!(breaker.allow_request("host"));
+	}
+
+	#[test]
+	fn half_open_probe_closes_on_success() {
+		let breaker = CircuitBreaker::new(
+			FailureClassifier::Require2xx,
+			1,
+			Duration::from_secs(30),
+			Duration::from_millis(1),
+		);
+
+		assert!(breaker.allow_request("host"));
+		breaker.record_result("host", Err(&err()));
+		assert!(!breaker.allow_request("host"));
+
+		std::thread::sleep(Duration::from_millis(5));
+
+		// Cooldown elapsed: a single probe is admitted, and a further request is rejected while
+		// that probe is outstanding.
+		assert!(breaker.allow_request("host"));
+		assert!(!breaker.allow_request("host"));
+
+		// A successful probe closes the breaker again.
+		breaker.record_result("host", Ok(()));
+		assert!(breaker.allow_request("host"));
+	}
+
+	#[test]
+	fn failed_probe_reopens_with_a_longer_cooldown() {
+		let breaker = CircuitBreaker::new(
+			FailureClassifier::Require2xx,
+			1,
+			Duration::from_secs(30),
+			Duration::from_millis(100),
+		);
+
+		assert!(breaker.allow_request("host"));
+		breaker.record_result("host", Err(&err()));
+		std::thread::sleep(Duration::from_millis(150));
+		assert!(breaker.allow_request("host"));
+		breaker.record_result("host", Err(&err()));
+
+		// Immediately after the failed probe the breaker is open again, and stays that way for
+		// longer than the original cooldown since it doubled (to 200ms).
+		assert!(!breaker.allow_request("host"));
+		std::thread::sleep(Duration::from_millis(150));
+		assert!(!breaker.allow_request("host"));
+	}
+
+	#[test]
+	fn allow4xx_below_ignores_expected_client_errors() {
+		let breaker = CircuitBreaker::new(
+			FailureClassifier::Allow4xxBelow(500),
+			1,
+			Duration::from_secs(30),
+			Duration::from_secs(60),
+		);
+
+		let not_found = VssError::NoSuchKeyError("missing".to_owned());
+		for _ in 0..10 {
+			assert!(breaker.allow_request("host"));
+			breaker.record_result("host", Err(&not_found));
+		}
+
+		// A 404 never counts as a failure under Allow4xxBelow(500), so the breaker never trips.
+		assert!(breaker.allow_request("host"));
+	}
+}
@@ -9,6 +9,11 @@ pub mod storable_builder;
 /// Contains retry utilities.
 pub mod retry;
 
+/// Contains the [`CircuitBreaker`] utility.
+///
+/// [`CircuitBreaker`]: circuit_breaker::CircuitBreaker
+pub mod circuit_breaker;
+
 /// Contains [`KeyObfuscator`] utility.
 ///
 /// [`KeyObfuscator`]: key_obfuscator::KeyObfuscator